@@ -0,0 +1,36 @@
+//! Shared diagnostic rendering for lex and parse errors
+//! Used by both the REPL and the file runner so error output is consistent
+//! across frontends
+
+use std::io::IsTerminal;
+use regex::Regex;
+
+/// Prints a colored diagnostic: the error message, and, when `position` is
+/// known, the offending source line with a caret under the reported column.
+/// Color is auto-disabled when stderr (the stream this writes to) is not a
+/// terminal (e.g. output is piped or redirected).
+pub fn print_diagnostic(source: &str, position: Option<(usize, usize)>, message: &str) {
+    let use_color = std::io::stderr().is_terminal();
+    let (red, reset) = if use_color { ("\x1b[31m", "\x1b[0m") } else { ("", "") };
+
+    eprintln!("{red}Error:{reset} {message}");
+
+    let Some((line, column)) = position else { return };
+    if let Some(source_line) = source.lines().nth(line.saturating_sub(1)) {
+        eprintln!("{}", source_line);
+        let padding = " ".repeat(column.saturating_sub(1));
+        eprintln!("{}{red}^{reset}", padding);
+    }
+}
+
+/// Extracts a `(line, column)` pair from a parser error message, matching
+/// the "line N, column M" wording lex errors in this codebase already use.
+/// Parser errors here carry only a `String`, so this is how `print_diagnostic`
+/// gets a position to draw a caret under for them too.
+pub fn extract_position(message: &str) -> Option<(usize, usize)> {
+    let re = Regex::new(r"line (\d+),?\s*column (\d+)").ok()?;
+    let caps = re.captures(message)?;
+    let line = caps.get(1)?.as_str().parse().ok()?;
+    let column = caps.get(2)?.as_str().parse().ok()?;
+    Some((line, column))
+}
@@ -3,6 +3,7 @@ use rustyline::error::ReadlineError;
 use std::fs;
 
 use crate::{lexer::{Lexer, LexError, Token}, parser::Parser, interpreter::Interpreter};
+use crate::cli::diagnostics::{print_diagnostic, extract_position};
 
 
 fn create_history_file_if_not_exists(filename: &str) -> std::io::Result<()> {
@@ -26,9 +27,87 @@ fn parse_tokens(tokens: Vec<Token>) -> Result<Vec<crate::parser::Stmt>, String>
     parser.parse()
 }
 
+/// Whether `source` looks like a complete statement at the character level:
+/// every paren/brace/bracket opened is closed, and no string literal is left
+/// open. The REPL uses this, rather than inspecting the parser's error
+/// message, to decide whether to keep buffering lines for a block spanning
+/// multiple lines before attempting to parse at all.
+fn looks_complete(source: &str) -> bool {
+    let mut depth: i64 = 0;
+    let mut in_string: Option<char> = None;
+    let mut chars = source.chars();
+
+    while let Some(c) = chars.next() {
+        if let Some(quote) = in_string {
+            if c == '\\' {
+                chars.next();
+            } else if c == quote {
+                in_string = None;
+            }
+            continue;
+        }
+
+        match c {
+            '"' | '\'' => in_string = Some(c),
+            '(' | '{' | '[' => depth += 1,
+            ')' | '}' | ']' => depth -= 1,
+            _ => {}
+        }
+    }
+
+    depth <= 0 && in_string.is_none()
+}
+
+fn print_help() {
+    println!("Nikl REPL meta-commands:");
+    println!("  :tokens <expr>  Print the tokens produced by the lexer for <expr>");
+    println!("  :ast <expr>     Print the statements produced by the parser for <expr>");
+    println!("  :clear          Reset the interpreter environment");
+    println!("  :help           Show this message");
+    println!("  exit            Exit the REPL");
+}
+
+/// Handles a colon-prefixed meta-command, returning `true` if `input` was one
+fn handle_meta_command(input: &str, interpreter: &mut Interpreter, base_path: &std::path::Path) -> bool {
+    if let Some(expr) = input.strip_prefix(":tokens") {
+        match tokenize_input(expr.trim()) {
+            Ok(tokens) => {
+                for token in &tokens {
+                    println!("{:?}", token);
+                }
+            }
+            Err(e) => eprintln!("Lex error: {:?}", e),
+        }
+        true
+    } else if let Some(expr) = input.strip_prefix(":ast") {
+        match tokenize_input(expr.trim()) {
+            Ok(tokens) => match parse_tokens(tokens) {
+                Ok(stmts) => {
+                    for stmt in &stmts {
+                        println!("{:?}", stmt);
+                    }
+                }
+                Err(e) => eprintln!("Parse error: {}", e),
+            },
+            Err(e) => eprintln!("Lex error: {:?}", e),
+        }
+        true
+    } else if input == ":help" {
+        print_help();
+        true
+    } else if input == ":clear" {
+        *interpreter = Interpreter::new(base_path.to_path_buf());
+        println!("Interpreter environment cleared.");
+        true
+    } else {
+        false
+    }
+}
+
 pub fn run_repl() -> rustyline::Result<()> {
     println!("Welcome to Nikl REPL!");
     println!("To exit, type 'exit' or press Ctrl+D");
+    println!("Type ':help' for a list of meta-commands");
 
     let mut rl = Editor::<(), FileHistory>::new()?;
     create_history_file_if_not_exists("/tmp/.nikl_history")?;
@@ -37,52 +116,83 @@ pub fn run_repl() -> rustyline::Result<()> {
     }
 
     let base_path = std::env::current_dir().unwrap_or_else(|_| std::path::PathBuf::from("."));
-    let mut interpreter = Interpreter::new(base_path);
+    let mut interpreter = Interpreter::new(base_path.clone());
+
+    // Lines accumulated so far for a statement spanning multiple lines
+    let mut buffer = String::new();
 
     loop {
-        let readline = rl.readline(">>> ");
+        let prompt = if buffer.is_empty() { ">>> " } else { "... " };
+        let readline = rl.readline(prompt);
 
         match readline {
             Ok(line) => {
-                let input = line.trim();
-                if input.is_empty() {
-                    continue;
+                // Set when a blank line is submitted while buffering, to force
+                // evaluation (and a real error, if any) instead of continuing
+                // to wait for more input
+                let mut force_eval = false;
+
+                if buffer.is_empty() {
+                    let input = line.trim();
+                    if input.is_empty() {
+                        continue;
+                    }
+                    if input == "exit" {
+                        break;
+                    }
+                    rl.add_history_entry(input)?;
+
+                    if handle_meta_command(input, &mut interpreter, &base_path) {
+                        continue;
+                    }
+
+                    buffer.push_str(input);
+                } else if line.trim().is_empty() {
+                    force_eval = true;
+                } else {
+                    buffer.push('\n');
+                    buffer.push_str(&line);
                 }
-                if input == "exit" {
-                    break;
+
+                if !force_eval && !looks_complete(&buffer) {
+                    // Keep buffering; re-prompt with the continuation prompt
+                    continue;
                 }
-                rl.add_history_entry(input)?;
 
-                match tokenize_input(input) {
+                match tokenize_input(&buffer) {
                     Ok(tokens) => {
-                        // If required, get the tokens for debugging
-                        // for token in &tokens {
-                        //     println!("{:?}", token);
-                        // }
-                        match parse_tokens(tokens.clone()) {
+                        match parse_tokens(tokens) {
                             Ok(stmts) => {
+                                buffer.clear();
                                 match interpreter.run(&stmts) {
                                     Ok(_) => (),
                                     Err(e) => eprintln!("Runtime error: {}", e),
                                 }
                             }
-                            Err(e) => eprintln!("Parse error: {}", e),
+                            Err(e) => {
+                                print_diagnostic(&buffer, extract_position(&e), &format!("Parse error: {}", e));
+                                buffer.clear();
+                            }
                         }
                     }
-                    Err(e) => match e {
-                        LexError::UnexpectedChar(ch, line, col) => {
-                            eprintln!("Unexpected character '{}' at line {}, column {}", ch, line, col);
-                        }
-                        LexError::UnterminatedString(line, col) => {
-                            eprintln!("Unterminated string starting at line {}, column {}", line, col);
-                        }
-                        LexError::InvalidNumber(num, line, col) => {
-                            eprintln!("Invalid number '{}' at line {}, column {}", num, line, col);
+                    Err(e) => {
+                        match e {
+                            LexError::UnexpectedChar(ch, line, col) => {
+                                print_diagnostic(&buffer, Some((line, col)), &format!("Unexpected character '{}'", ch));
+                            }
+                            LexError::UnterminatedString(line, col) => {
+                                print_diagnostic(&buffer, Some((line, col)), "Unterminated string");
+                            }
+                            LexError::InvalidNumber(num, line, col) => {
+                                print_diagnostic(&buffer, Some((line, col)), &format!("Invalid number '{}'", num));
+                            }
                         }
-                    },
+                        buffer.clear();
+                    }
                 }
             }
             Err(ReadlineError::Interrupted) => {
+                buffer.clear();
                 println!("Keyboard Interrupt");
                 continue;
             }
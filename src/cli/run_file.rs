@@ -1,9 +1,29 @@
 use std::fs;
+use std::io::{self, IsTerminal, Read};
 use std::path::{Path, PathBuf};
 
 use crate::{lexer::{Lexer, LexError, Token}, parser::Parser, interpreter::Interpreter};
+use crate::cli::diagnostics::{print_diagnostic, extract_position};
 
 
+/// Reads the whole program from stdin, for `nikl -` or a piped invocation
+/// with no file argument (`echo '...' | nikl`)
+fn read_stdin() -> Option<String> {
+    let mut bytes = Vec::new();
+    if let Err(e) = io::stdin().read_to_end(&mut bytes) {
+        eprintln!("Error reading program from stdin: {}", e);
+        return None;
+    }
+
+    match String::from_utf8(bytes) {
+        Ok(content) => Some(content),
+        Err(e) => {
+            eprintln!("Error: program read from stdin is not valid UTF-8: {}", e);
+            None
+        }
+    }
+}
+
 fn check_file_is_valid(filename: &str) -> bool {
     match fs::metadata(filename) {
         Ok(metadata) if metadata.is_file() && filename.ends_with(".nk") => {
@@ -54,49 +74,92 @@ fn interpret_statements(stmts: &[crate::parser::Stmt], base_path: PathBuf) -> Re
     interpreter.run(stmts).map(|_| ())
 }
 
-pub fn run_file(filename: &str) {
-    if let Some(content) = read_file(filename) {
-        match tokenize_input(&content) {
-            Ok(tokens) => {
-                // If required, log the tokens
-                // for token in &tokens {
-                //     println!("{:?}", token);
-                // }
-                match parse_tokens(tokens.clone()) {
-                    Ok(stmts) => {
-                        // If required, log the parsed statements
-                        // for stmt in &stmts {
-                        //     println!("{:?}", stmt);
-                        // }
-
-                        // Extract the directory containing the file
-                        let base_path = Path::new(filename)
-                            .parent()
-                            .unwrap_or_else(|| Path::new("."))
-                            .to_path_buf();
-
-                        // Execute the statements
-                        match interpret_statements(&stmts, base_path) {
-                            Ok(_) => (),    // Successfully executed
-                            Err(e) => eprintln!("Error executing script: {}", e),
-                        }
+/// Tokenizes, parses, and interprets a program's source, reporting any
+/// lex/parse/runtime error to stderr the same way regardless of where the
+/// source came from (a real file, stdin, or a piped "-" argument)
+fn run_source(content: &str, base_path: PathBuf) {
+    match tokenize_input(content) {
+        Ok(tokens) => {
+            // If required, log the tokens
+            // for token in &tokens {
+            //     println!("{:?}", token);
+            // }
+            match parse_tokens(tokens.clone()) {
+                Ok(stmts) => {
+                    // If required, log the parsed statements
+                    // for stmt in &stmts {
+                    //     println!("{:?}", stmt);
+                    // }
+
+                    // Execute the statements
+                    match interpret_statements(&stmts, base_path) {
+                        Ok(_) => (),    // Successfully executed
+                        Err(e) => eprintln!("Error executing script: {}", e),
                     }
-                    Err(e) => eprintln!("Error parsing statements: {}", e),
                 }
+                Err(e) => print_diagnostic(content, extract_position(&e), &format!("Error parsing statements: {}", e)),
             }
-            Err(e) => match e {
-                LexError::UnexpectedChar(ch, line, col) => {
-                    eprintln!("Unexpected character '{}' at line {}, column {}", ch, line, col);
-                }
-                LexError::UnterminatedString(line, col) => {
-                    eprintln!("Unterminated string starting at line {}, column {}", line, col);
-                }
-                LexError::InvalidNumber(num, line, col) => {
-                    eprintln!("Invalid number '{}' at line {}, column {}", num, line, col);
-                }
-            },
         }
+        Err(e) => match e {
+            LexError::UnexpectedChar(ch, line, col) => {
+                print_diagnostic(content, Some((line, col)), &format!("Unexpected character '{}'", ch));
+            }
+            LexError::UnterminatedString(line, col) => {
+                print_diagnostic(content, Some((line, col)), "Unterminated string");
+            }
+            LexError::InvalidNumber(num, line, col) => {
+                print_diagnostic(content, Some((line, col)), &format!("Invalid number '{}'", num));
+            }
+        },
+    }
+}
+
+/// Runs a `.nk` program from `filename`. Pass `-` to read the program from
+/// stdin instead, e.g. `echo '...' | nikl -`; the current working directory
+/// is used as the base path in that case since there is no real file.
+pub fn run_file(filename: &str) {
+    if filename == "-" {
+        run_stdin();
+        return;
+    }
+
+    if let Some(content) = read_file(filename) {
+        let base_path = Path::new(filename)
+            .parent()
+            .unwrap_or_else(|| Path::new("."))
+            .to_path_buf();
+        run_source(&content, base_path);
     } else {
         eprintln!("Failed to read or validate the file '{}'", filename);
     }
 }
+
+/// Runs a program read from stdin. Used for `nikl -` and for the no-argument
+/// case in the CLI dispatch when stdin is not a TTY.
+pub fn run_stdin() {
+    if let Some(content) = read_stdin() {
+        let base_path = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+        run_source(&content, base_path);
+    } else {
+        eprintln!("Failed to read program from stdin");
+    }
+}
+
+/// CLI dispatch for the file argument: runs `filename` if one was given,
+/// otherwise auto-detects a piped program (`echo '...' | nikl`) by checking
+/// whether stdin is attached to a terminal. Returns `true` if a program was
+/// run, or `false` if the caller should fall back to starting the REPL
+/// (no file argument and stdin is an interactive terminal).
+pub fn run_file_or_stdin(filename: Option<&str>) -> bool {
+    match filename {
+        Some(f) => {
+            run_file(f);
+            true
+        }
+        None if !io::stdin().is_terminal() => {
+            run_stdin();
+            true
+        }
+        None => false,
+    }
+}
@@ -5,6 +5,14 @@
 use std::io::{self, Write};
 use regex::Regex;
 use crate::interpreter::value::Value;
+use crate::interpreter::Interpreter;
+
+
+/// Signature used to invoke a `Value` as a callable from within a builtin.
+/// `map`/`filter`/`reduce` take this so they can call the user-supplied
+/// function value once per element without knowing how the interpreter
+/// represents callables internally
+pub type CallValue = fn(&mut Interpreter, Value, Vec<Value>) -> Result<Value, String>;
 
 
 /// Unescapes a string by replacing escape sequences with their corresponding characters
@@ -44,13 +52,15 @@ pub fn builtin_print(args: Vec<Value>) -> Result<Value, String> {
 
 /// Built-in function to get the length of any possible type
 /// Currently only works on strings
+/// Strings are measured in Unicode scalar values (chars), not bytes,
+/// so multi-byte characters are counted once each
 pub fn builtin_len(args: Vec<Value>) -> Result<Value, String> {
     if args.len() != 1 {
         return Err("len() takes exactly one argument".to_string());
     }
 
     match &args[0] {
-        Value::String(s) => Ok(Value::Integer(s.len() as i64)),
+        Value::String(s) => Ok(Value::Integer(s.chars().count() as i64)),
         Value::Array(a) => Ok(Value::Integer(a.len() as i64)),
         Value::Tuple(t) => Ok(Value::Integer(t.len() as i64)),
         Value::HashMap(h) => Ok(Value::Integer(h.len() as i64)),
@@ -59,6 +69,63 @@ pub fn builtin_len(args: Vec<Value>) -> Result<Value, String> {
 }
 
 
+/// Built-in function to get the character at a given index in a string
+/// Indexing is by Unicode scalar value, not byte offset, and is zero-based
+pub fn builtin_char_at(args: Vec<Value>) -> Result<Value, String> {
+    if args.len() != 2 {
+        return Err("char_at() takes exactly two arguments".to_string());
+    }
+
+    let s = match &args[0] {
+        Value::String(s) => s,
+        _ => return Err(format!("char_at() expects a string as its first argument, but got {:?}", args[0])),
+    };
+
+    let index = match &args[1] {
+        Value::Integer(i) => *i,
+        _ => return Err(format!("char_at() expects an integer as its second argument, but got {:?}", args[1])),
+    };
+
+    let chars: Vec<char> = s.chars().collect();
+    if index < 0 || index as usize >= chars.len() {
+        return Err(format!("char_at() index {} out of range for string of length {}", index, chars.len()));
+    }
+
+    Ok(Value::String(chars[index as usize].to_string()))
+}
+
+
+/// Built-in function to slice a string between two Unicode scalar indices
+/// `start` is inclusive and `end` is exclusive, matching array slicing semantics
+pub fn builtin_slice(args: Vec<Value>) -> Result<Value, String> {
+    if args.len() != 3 {
+        return Err("slice() takes exactly three arguments".to_string());
+    }
+
+    let s = match &args[0] {
+        Value::String(s) => s,
+        _ => return Err(format!("slice() expects a string as its first argument, but got {:?}", args[0])),
+    };
+
+    let start = match &args[1] {
+        Value::Integer(i) => *i,
+        _ => return Err(format!("slice() expects an integer as its second argument, but got {:?}", args[1])),
+    };
+
+    let end = match &args[2] {
+        Value::Integer(i) => *i,
+        _ => return Err(format!("slice() expects an integer as its third argument, but got {:?}", args[2])),
+    };
+
+    let chars: Vec<char> = s.chars().collect();
+    if start < 0 || end < 0 || start > end || end as usize > chars.len() {
+        return Err(format!("slice() range {}..{} out of range for string of length {}", start, end, chars.len()));
+    }
+
+    Ok(Value::String(chars[start as usize..end as usize].iter().collect()))
+}
+
+
 /// Built-in function to convert a value to a string
 /// Currently only works on strings, integers, floats, and booleans
 pub fn builtin_str(args: Vec<Value>) -> Result<Value, String> {
@@ -177,6 +244,269 @@ pub fn builtin_type(args: Vec<Value>) -> Result<Value, String> {
 }
 
 
+/// Built-in function to split a string on a separator
+/// Returns an array of strings, mirroring Rust's `str::split`
+pub fn builtin_split(args: Vec<Value>) -> Result<Value, String> {
+    if args.len() != 2 {
+        return Err("split() takes exactly two arguments".to_string());
+    }
+
+    match (&args[0], &args[1]) {
+        (Value::String(s), Value::String(sep)) => {
+            let parts = s.split(sep.as_str())
+                .map(|p| Value::String(p.to_string()))
+                .collect();
+            Ok(Value::Array(parts))
+        }
+        _ => Err(format!("split() expects two strings, but got {:?} and {:?}", args[0], args[1])),
+    }
+}
+
+
+/// Built-in function to join an array of strings with a separator
+pub fn builtin_join(args: Vec<Value>) -> Result<Value, String> {
+    if args.len() != 2 {
+        return Err("join() takes exactly two arguments".to_string());
+    }
+
+    let sep = match &args[1] {
+        Value::String(sep) => sep,
+        _ => return Err(format!("join() expects a string as its second argument, but got {:?}", args[1])),
+    };
+
+    match &args[0] {
+        Value::Array(a) => {
+            let parts: Result<Vec<String>, String> = a.iter().map(|v| match v {
+                Value::String(s) => Ok(s.clone()),
+                _ => Err(format!("join() expects an array of strings, but got {:?}", v)),
+            }).collect();
+            Ok(Value::String(parts?.join(sep)))
+        }
+        _ => Err(format!("join() expects an array as its first argument, but got {:?}", args[0])),
+    }
+}
+
+
+/// Built-in function to check whether a string contains a substring
+pub fn builtin_contains(args: Vec<Value>) -> Result<Value, String> {
+    if args.len() != 2 {
+        return Err("contains() takes exactly two arguments".to_string());
+    }
+
+    match (&args[0], &args[1]) {
+        (Value::String(s), Value::String(needle)) => Ok(Value::Bool(s.contains(needle.as_str()))),
+        _ => Err(format!("contains() expects two strings, but got {:?} and {:?}", args[0], args[1])),
+    }
+}
+
+
+/// Built-in function to check whether a string starts with a prefix
+pub fn builtin_starts_with(args: Vec<Value>) -> Result<Value, String> {
+    if args.len() != 2 {
+        return Err("starts_with() takes exactly two arguments".to_string());
+    }
+
+    match (&args[0], &args[1]) {
+        (Value::String(s), Value::String(prefix)) => Ok(Value::Bool(s.starts_with(prefix.as_str()))),
+        _ => Err(format!("starts_with() expects two strings, but got {:?} and {:?}", args[0], args[1])),
+    }
+}
+
+
+/// Built-in function to check whether a string ends with a suffix
+pub fn builtin_ends_with(args: Vec<Value>) -> Result<Value, String> {
+    if args.len() != 2 {
+        return Err("ends_with() takes exactly two arguments".to_string());
+    }
+
+    match (&args[0], &args[1]) {
+        (Value::String(s), Value::String(suffix)) => Ok(Value::Bool(s.ends_with(suffix.as_str()))),
+        _ => Err(format!("ends_with() expects two strings, but got {:?} and {:?}", args[0], args[1])),
+    }
+}
+
+
+/// Built-in function to replace all occurrences of a substring with another
+pub fn builtin_replace(args: Vec<Value>) -> Result<Value, String> {
+    if args.len() != 3 {
+        return Err("replace() takes exactly three arguments".to_string());
+    }
+
+    match (&args[0], &args[1], &args[2]) {
+        (Value::String(s), Value::String(from), Value::String(to)) => {
+            Ok(Value::String(s.replace(from.as_str(), to)))
+        }
+        _ => Err(format!("replace() expects three strings, but got {:?}, {:?}, and {:?}", args[0], args[1], args[2])),
+    }
+}
+
+
+/// Built-in function to convert a string to uppercase
+pub fn builtin_upper(args: Vec<Value>) -> Result<Value, String> {
+    if args.len() != 1 {
+        return Err("upper() takes exactly one argument".to_string());
+    }
+
+    match &args[0] {
+        Value::String(s) => Ok(Value::String(s.to_uppercase())),
+        _ => Err(format!("upper() expects a string, but got {:?}", args[0])),
+    }
+}
+
+
+/// Built-in function to convert a string to lowercase
+pub fn builtin_lower(args: Vec<Value>) -> Result<Value, String> {
+    if args.len() != 1 {
+        return Err("lower() takes exactly one argument".to_string());
+    }
+
+    match &args[0] {
+        Value::String(s) => Ok(Value::String(s.to_lowercase())),
+        _ => Err(format!("lower() expects a string, but got {:?}", args[0])),
+    }
+}
+
+
+/// Built-in function to trim leading and trailing whitespace from a string
+pub fn builtin_trim(args: Vec<Value>) -> Result<Value, String> {
+    if args.len() != 1 {
+        return Err("trim() takes exactly one argument".to_string());
+    }
+
+    match &args[0] {
+        Value::String(s) => Ok(Value::String(s.trim().to_string())),
+        _ => Err(format!("trim() expects a string, but got {:?}", args[0])),
+    }
+}
+
+
+/// Built-in function to build an array of integers
+/// `range(n)` counts from 0 up to (but excluding) `n`
+/// `range(start, end)` counts from `start` up to (but excluding) `end`
+/// `range(start, end, step)` additionally steps by `step`, which may be negative
+pub fn builtin_range(args: Vec<Value>) -> Result<Value, String> {
+    let (start, end, step) = match args.len() {
+        1 => {
+            let end = match &args[0] {
+                Value::Integer(i) => *i,
+                _ => return Err(format!("range() expects an integer argument, but got {:?}", args[0])),
+            };
+            (0, end, 1)
+        }
+        2 | 3 => {
+            let start = match &args[0] {
+                Value::Integer(i) => *i,
+                _ => return Err(format!("range() expects an integer argument, but got {:?}", args[0])),
+            };
+            let end = match &args[1] {
+                Value::Integer(i) => *i,
+                _ => return Err(format!("range() expects an integer argument, but got {:?}", args[1])),
+            };
+            let step = if args.len() == 3 {
+                match &args[2] {
+                    Value::Integer(i) => *i,
+                    _ => return Err(format!("range() expects an integer argument, but got {:?}", args[2])),
+                }
+            } else {
+                1
+            };
+            (start, end, step)
+        }
+        _ => return Err("range() takes one, two, or three arguments".to_string()),
+    };
+
+    if step == 0 {
+        return Err("range() step cannot be zero".to_string());
+    }
+
+    let mut values = Vec::new();
+    let mut i = start;
+    if step > 0 {
+        while i < end {
+            values.push(Value::Integer(i));
+            i += step;
+        }
+    } else {
+        while i > end {
+            values.push(Value::Integer(i));
+            i += step;
+        }
+    }
+
+    Ok(Value::Array(values))
+}
+
+
+/// Built-in function to apply a callable to every element of an array,
+/// returning a new array of the results
+pub fn builtin_map(interpreter: &mut Interpreter, args: Vec<Value>, call: CallValue) -> Result<Value, String> {
+    if args.len() != 2 {
+        return Err("map() takes exactly two arguments".to_string());
+    }
+
+    let elements = match &args[0] {
+        Value::Array(a) => a.clone(),
+        _ => return Err(format!("map() expects an array as its first argument, but got {:?}", args[0])),
+    };
+    let func = args[1].clone();
+
+    let mut results = Vec::with_capacity(elements.len());
+    for element in elements {
+        results.push(call(interpreter, func.clone(), vec![element])?);
+    }
+
+    Ok(Value::Array(results))
+}
+
+
+/// Built-in function to keep only the elements of an array for which a
+/// callable returns a truthy `Value::Bool`
+pub fn builtin_filter(interpreter: &mut Interpreter, args: Vec<Value>, call: CallValue) -> Result<Value, String> {
+    if args.len() != 2 {
+        return Err("filter() takes exactly two arguments".to_string());
+    }
+
+    let elements = match &args[0] {
+        Value::Array(a) => a.clone(),
+        _ => return Err(format!("filter() expects an array as its first argument, but got {:?}", args[0])),
+    };
+    let func = args[1].clone();
+
+    let mut results = Vec::new();
+    for element in elements {
+        match call(interpreter, func.clone(), vec![element.clone()])? {
+            Value::Bool(true) => results.push(element),
+            Value::Bool(false) => (),
+            other => return Err(format!("filter() callback must return a boolean, but got {:?}", other)),
+        }
+    }
+
+    Ok(Value::Array(results))
+}
+
+
+/// Built-in function to fold an array down to a single value using a
+/// callable of the form `fn(accumulator, element) -> value`
+pub fn builtin_reduce(interpreter: &mut Interpreter, args: Vec<Value>, call: CallValue) -> Result<Value, String> {
+    if args.len() != 3 {
+        return Err("reduce() takes exactly three arguments".to_string());
+    }
+
+    let elements = match &args[0] {
+        Value::Array(a) => a.clone(),
+        _ => return Err(format!("reduce() expects an array as its first argument, but got {:?}", args[0])),
+    };
+    let func = args[1].clone();
+    let mut accumulator = args[2].clone();
+
+    for element in elements {
+        accumulator = call(interpreter, func.clone(), vec![accumulator, element])?;
+    }
+
+    Ok(accumulator)
+}
+
+
 /// Built-in function to get input from the user
 /// Currently only works with strings
 /// Returns the input as a string
@@ -0,0 +1,50 @@
+//! Registers built-in functions under the names scripts call them by
+//!
+//! The interpreter consults `call_builtin` when resolving a function call
+//! that isn't user-defined; each arm forwards to the implementation in
+//! `builtin_core`.
+
+pub mod builtin_core;
+
+use crate::interpreter::Interpreter;
+use crate::interpreter::value::Value;
+use builtin_core::*;
+
+/// Looks up and calls a builtin by name. Returns `None` if `name` isn't a
+/// builtin, so the interpreter can fall through to looking up a
+/// user-defined function instead.
+pub fn call_builtin(name: &str, args: Vec<Value>, interpreter: &mut Interpreter) -> Option<Result<Value, String>> {
+    Some(match name {
+        "print" => builtin_print(args),
+        "len" => builtin_len(args),
+        "str" => builtin_str(args),
+        "int" => builtin_int(args),
+        "float" => builtin_float(args),
+        "bool" => builtin_bool(args),
+        "exit" => builtin_exit(args),
+        "type" => builtin_type(args),
+        "input" => builtin_input(args),
+        "char_at" => builtin_char_at(args),
+        "slice" => builtin_slice(args),
+        "split" => builtin_split(args),
+        "join" => builtin_join(args),
+        "contains" => builtin_contains(args),
+        "starts_with" => builtin_starts_with(args),
+        "ends_with" => builtin_ends_with(args),
+        "replace" => builtin_replace(args),
+        "upper" => builtin_upper(args),
+        "lower" => builtin_lower(args),
+        "trim" => builtin_trim(args),
+        "range" => builtin_range(args),
+        "map" => builtin_map(interpreter, args, call_value),
+        "filter" => builtin_filter(interpreter, args, call_value),
+        "reduce" => builtin_reduce(interpreter, args, call_value),
+        _ => return None,
+    })
+}
+
+/// The concrete `CallValue` used to let `map`/`filter`/`reduce` invoke a
+/// script-supplied function value through the interpreter
+fn call_value(interpreter: &mut Interpreter, func: Value, args: Vec<Value>) -> Result<Value, String> {
+    interpreter.call_function(func, args)
+}